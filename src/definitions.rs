@@ -0,0 +1,67 @@
+use crate::app::{Cost, CostGrowth, DEFAULT_MAX_OFFLINE_SECS, GameState, Resource, ResourceType};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// On-disk description of a single resource's starting stats and upgrade-cost curve.
+///
+/// Letting these live in a config file rather than [`GameState::default`] is what lets
+/// modders add resources (e.g. `"Gold"`, `"Gems"`) or rebalance existing ones without
+/// recompiling.
+#[derive(Debug, Deserialize)]
+pub struct ResourceDefinition {
+    pub id: ResourceType,
+    pub progress_per_tick: f64,
+    #[serde(default)]
+    pub starting_amount: u64,
+    pub cost_amount: u64,
+    pub cost_resource: ResourceType,
+    #[serde(default)]
+    pub cost_growth: CostGrowth,
+}
+
+/// The full game economy, as declared by a `game.toml` definition file.
+#[derive(Debug, Deserialize)]
+pub struct GameDefinition {
+    pub resources: Vec<ResourceDefinition>,
+    /// Cap, in seconds, on how much offline time a "welcome back" catch-up will credit.
+    /// Absent falls back to [`DEFAULT_MAX_OFFLINE_SECS`] rather than going uncapped — an
+    /// absence of months would otherwise replay millions of ticks (and, with `on_tick`
+    /// scripts installed, that many full-state Lua round-trips) on load.
+    #[serde(default)]
+    pub max_offline_secs: Option<f64>,
+}
+
+impl From<GameDefinition> for GameState {
+    fn from(definition: GameDefinition) -> Self {
+        Self {
+            resources: definition
+                .resources
+                .into_iter()
+                .map(|def| {
+                    Resource::new(
+                        def.id,
+                        def.progress_per_tick,
+                        Cost::new(def.cost_amount, def.cost_resource),
+                    )
+                    .start_with(def.starting_amount)
+                    .with_cost_growth(def.cost_growth)
+                })
+                .collect(),
+            max_offline_secs: Some(definition.max_offline_secs.unwrap_or(DEFAULT_MAX_OFFLINE_SECS)),
+        }
+    }
+}
+
+/// Loads a [`GameState`] from the game-definition file at `path`, falling back to the
+/// built-in default economy if the file is missing or fails to parse.
+pub fn load_game_state(path: &Path) -> GameState {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return GameState::default();
+    };
+
+    match toml::from_str::<GameDefinition>(&raw) {
+        Ok(definition) => definition.into(),
+        Err(_) => GameState::default(),
+    }
+}
@@ -0,0 +1,133 @@
+use color_eyre::eyre::OptionExt;
+use futures::{FutureExt, StreamExt};
+use crossterm::event::EventStream;
+use ratatui::crossterm::event::Event as CrosstermEvent;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// The frequency at which tick events are emitted.
+pub const TICK_FPS: f64 = 30.0;
+
+/// The frequency at which frame (render) events are emitted.
+///
+/// Kept independent of [`TICK_FPS`] so game logic and rendering can run at different
+/// cadences: redrawing faster or slower than the economy advances should never change
+/// how many ticks are simulated.
+pub const FRAME_FPS: f64 = 30.0;
+
+/// Representation of all possible events.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// An event emitted on a regular schedule to advance game logic.
+    Tick,
+    /// An event emitted on a regular schedule to request a redraw.
+    Frame,
+    /// Crossterm events.
+    Crossterm(CrosstermEvent),
+    /// Application events.
+    App(AppEvent),
+}
+
+/// Application events.
+///
+/// Also doubles as the keymap config's action identifier (see [`crate::keymap`]), so it
+/// derives [`Deserialize`] with the config's `snake_case` action names.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppEvent {
+    /// Move the selection down.
+    GoDown,
+    /// Move the selection up.
+    GoUp,
+    /// Upgrade the selected resource.
+    Upgrade,
+    /// Dismiss the "welcome back" offline-earnings summary.
+    DismissSummary,
+    /// Quit the application.
+    Quit,
+}
+
+/// Terminal event handler.
+#[derive(Debug)]
+pub struct EventHandler {
+    /// Event sender channel.
+    sender: mpsc::UnboundedSender<Event>,
+    /// Event receiver channel.
+    receiver: mpsc::UnboundedReceiver<Event>,
+}
+
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventHandler {
+    /// Constructs a new instance of [`EventHandler`] and spawns a task to handle events.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let actor = EventThread::new(sender.clone());
+        tokio::spawn(async move { actor.run().await });
+        Self { sender, receiver }
+    }
+
+    /// Receives an event from the sender.
+    pub async fn next(&mut self) -> color_eyre::Result<Event> {
+        self.receiver
+            .recv()
+            .await
+            .ok_or_eyre("failed to receive event, the event thread must have panicked")
+    }
+
+    /// Queue an app event to be sent to the event receiver.
+    pub fn send(&self, app_event: AppEvent) {
+        let _ = self.sender.send(Event::App(app_event));
+    }
+}
+
+/// A task that merges crossterm input, a tick timer and a frame timer into a single
+/// stream of [`Event`]s.
+///
+/// Keeping the timers independent means flooding input (or a slow terminal redraw) never
+/// perturbs how many ticks the game simulates, and a future background task (e.g.
+/// autosave) can feed the same channel without blocking the UI.
+struct EventThread {
+    sender: mpsc::UnboundedSender<Event>,
+}
+
+impl EventThread {
+    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
+        Self { sender }
+    }
+
+    async fn run(self) -> color_eyre::Result<()> {
+        let mut reader = EventStream::new();
+        let mut tick = tokio::time::interval(Duration::from_secs_f64(1.0 / TICK_FPS));
+        let mut frame = tokio::time::interval(Duration::from_secs_f64(1.0 / FRAME_FPS));
+        loop {
+            let tick_delay = tick.tick();
+            let frame_delay = frame.tick();
+            let crossterm_event = reader.next().fuse();
+            tokio::select! {
+                _ = self.sender.closed() => {
+                    break;
+                }
+                _ = tick_delay => {
+                    self.send(Event::Tick);
+                }
+                _ = frame_delay => {
+                    self.send(Event::Frame);
+                }
+                Some(Ok(evt)) = crossterm_event => {
+                    self.send(Event::Crossterm(evt));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&self, event: Event) {
+        let _ = self.sender.send(event);
+    }
+}
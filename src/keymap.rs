@@ -0,0 +1,126 @@
+use crate::event::AppEvent;
+use color_eyre::eyre::{WrapErr, eyre};
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Every action a key must be able to trigger; a keymap that can't reach one of these
+/// is unplayable, so [`Keymap::parse`] rejects it rather than leaving the player stuck.
+const REQUIRED_ACTIONS: [AppEvent; 4] = [
+    AppEvent::GoDown,
+    AppEvent::GoUp,
+    AppEvent::Upgrade,
+    AppEvent::Quit,
+];
+
+/// A single `[[bindings]]` entry (`key`, optional `modifiers`, `action`) in the keymap
+/// config, before parsing `key`/`modifiers` into crossterm's types.
+#[derive(Debug, Deserialize)]
+struct KeymapEntry {
+    key: String,
+    #[serde(default)]
+    modifiers: Vec<String>,
+    action: AppEvent,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeymapConfig {
+    #[serde(default)]
+    bindings: Vec<KeymapEntry>,
+}
+
+/// Maps key chords to the [`AppEvent`] they trigger, replacing the hardcoded
+/// `match key_event.code` arms so players can remap navigation (e.g. to j/k) or add new
+/// chords without recompiling.
+#[derive(Debug)]
+pub struct Keymap {
+    bindings: HashMap<(KeyCode, KeyModifiers), AppEvent>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = HashMap::from([
+            ((KeyCode::Esc, KeyModifiers::NONE), AppEvent::Quit),
+            ((KeyCode::Char('q'), KeyModifiers::NONE), AppEvent::Quit),
+            ((KeyCode::Char('c'), KeyModifiers::CONTROL), AppEvent::Quit),
+            ((KeyCode::Char('C'), KeyModifiers::CONTROL), AppEvent::Quit),
+            ((KeyCode::Down, KeyModifiers::NONE), AppEvent::GoDown),
+            ((KeyCode::Up, KeyModifiers::NONE), AppEvent::GoUp),
+            ((KeyCode::Enter, KeyModifiers::NONE), AppEvent::Upgrade),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Looks up the action bound to a key chord, if any.
+    pub fn lookup(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<AppEvent> {
+        self.bindings.get(&(code, modifiers)).cloned()
+    }
+
+    /// Loads keybindings from `path`, falling back to [`Keymap::default`] if the file is
+    /// absent or fails to parse/validate.
+    pub fn load_or_default(path: &Path) -> Self {
+        let Ok(raw) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        Self::parse(&raw).unwrap_or_default()
+    }
+
+    fn parse(raw: &str) -> color_eyre::Result<Self> {
+        let config: KeymapConfig =
+            toml::from_str(raw).wrap_err("failed to parse keymap config")?;
+
+        let mut bindings = HashMap::new();
+        for entry in config.bindings {
+            let code = parse_key_code(&entry.key)?;
+            let modifiers = parse_modifiers(&entry.modifiers)?;
+            bindings.insert((code, modifiers), entry.action);
+        }
+
+        let keymap = Self { bindings };
+        keymap.validate()?;
+        Ok(keymap)
+    }
+
+    /// Ensures every action in [`REQUIRED_ACTIONS`] has at least one binding.
+    fn validate(&self) -> color_eyre::Result<()> {
+        for required in &REQUIRED_ACTIONS {
+            if !self.bindings.values().any(|action| action == required) {
+                return Err(eyre!("keymap config has no binding for action {required:?}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_key_code(key: &str) -> color_eyre::Result<KeyCode> {
+    let code = match key {
+        "esc" => KeyCode::Esc,
+        "enter" => KeyCode::Enter,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        single if single.chars().count() == 1 => KeyCode::Char(single.chars().next().unwrap()),
+        other => return Err(eyre!("unrecognized key name in keymap config: {other:?}")),
+    };
+    Ok(code)
+}
+
+fn parse_modifiers(modifiers: &[String]) -> color_eyre::Result<KeyModifiers> {
+    let mut parsed = KeyModifiers::NONE;
+    for modifier in modifiers {
+        parsed |= match modifier.as_str() {
+            "shift" => KeyModifiers::SHIFT,
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            other => return Err(eyre!("unrecognized modifier in keymap config: {other:?}")),
+        };
+    }
+    Ok(parsed)
+}
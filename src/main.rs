@@ -0,0 +1,19 @@
+use crate::app::App;
+
+pub mod app;
+pub mod definitions;
+pub mod event;
+pub mod keymap;
+pub mod paths;
+pub mod save;
+pub mod scripting;
+pub mod ui;
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+    let terminal = ratatui::init();
+    let result = App::new().run(terminal).await;
+    ratatui::restore();
+    result
+}
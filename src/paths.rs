@@ -0,0 +1,54 @@
+use color_eyre::eyre::eyre;
+use directories::ProjectDirs;
+use std::fs;
+use std::path::PathBuf;
+
+/// The slot used for a fresh install with no saves yet.
+pub const DEFAULT_SLOT: &str = "default";
+
+/// Resolves the OS-appropriate data directory for save files (e.g.
+/// `~/.local/share/incremental-tui` on Linux), creating it if it doesn't exist yet.
+fn save_dir() -> color_eyre::Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "incremental-tui")
+        .ok_or_else(|| eyre!("could not determine a home directory to store saves in"))?;
+    let dir = project_dirs.data_dir().to_path_buf();
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// The save file path for a named slot, e.g. `~/.local/share/incremental-tui/default.json`.
+pub fn save_file_path(slot: &str) -> color_eyre::Result<PathBuf> {
+    Ok(save_dir()?.join(format!("{slot}.json")))
+}
+
+/// Resolves the OS-appropriate config directory (e.g. `~/.config/incremental-tui` on
+/// Linux). Doesn't create it, since everything read from it (scripts, keymap, game
+/// definition) is optional and a missing directory just means "nothing configured".
+pub fn config_dir() -> color_eyre::Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "incremental-tui")
+        .ok_or_else(|| eyre!("could not determine a home directory to read config from"))?;
+    Ok(project_dirs.config_dir().to_path_buf())
+}
+
+/// The directory user Lua scripts are loaded from, e.g.
+/// `~/.config/incremental-tui/scripts`.
+pub fn scripts_dir() -> color_eyre::Result<PathBuf> {
+    Ok(config_dir()?.join("scripts"))
+}
+
+/// Lists the names of existing save slots, sorted alphabetically.
+pub fn list_slots() -> color_eyre::Result<Vec<String>> {
+    let dir = save_dir()?;
+    let mut slots = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+            slots.push(stem.to_string());
+        }
+    }
+    slots.sort();
+    Ok(slots)
+}
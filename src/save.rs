@@ -0,0 +1,125 @@
+use crate::app::GameState;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Current on-disk save schema version.
+///
+/// Bump this and append a migration to [`MIGRATIONS`] whenever `GameState`'s shape
+/// changes in a way that would break deserializing an older save directly.
+pub const CURRENT_SAVE_VERSION: u32 = 3;
+
+/// Versioned wrapper persisted to disk in place of a bare [`GameState`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub state: GameState,
+}
+
+impl SaveFile {
+    pub fn new(state: GameState) -> Self {
+        Self {
+            version: CURRENT_SAVE_VERSION,
+            state,
+        }
+    }
+}
+
+/// Ordered chain of migrations, one per version bump. Each function takes the raw
+/// `state` value as it looked at version `n` and returns its equivalent at `n + 1`.
+const MIGRATIONS: &[fn(Value) -> Value] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// v1 saves were a bare `GameState` with no wrapper at all, so the state value itself
+/// doesn't change shape here; this migration only exists to anchor the version number.
+fn migrate_v1_to_v2(state: Value) -> Value {
+    state
+}
+
+/// v2 saves predate per-resource cost-growth curves, so each resource gets the
+/// original fixed `Exponential` formula backfilled.
+fn migrate_v2_to_v3(mut state: Value) -> Value {
+    if let Some(resources) = state.get_mut("resources").and_then(Value::as_array_mut) {
+        for resource in resources {
+            if let Some(resource) = resource.as_object_mut() {
+                resource
+                    .entry("cost_growth")
+                    .or_insert_with(|| Value::String("Exponential".to_string()));
+            }
+        }
+    }
+    state
+}
+
+/// Parses a save file's raw JSON, detecting unversioned legacy saves (a bare
+/// `GameState`, treated as v1) and running it through [`MIGRATIONS`] up to
+/// [`CURRENT_SAVE_VERSION`] before final deserialization.
+pub fn load_and_migrate(raw: &str) -> color_eyre::Result<GameState> {
+    let value: Value = serde_json::from_str(raw).wrap_err("failed to parse save file")?;
+
+    let (mut version, mut state) = match value {
+        Value::Object(ref map) if map.contains_key("version") && map.contains_key("state") => {
+            let version = map["version"].as_u64().unwrap_or(1) as u32;
+            (version, map["state"].clone())
+        }
+        legacy => (1, legacy),
+    };
+
+    if !(1..=CURRENT_SAVE_VERSION).contains(&version) {
+        return Err(color_eyre::eyre::eyre!(
+            "save file has unsupported version {version} (expected 1..={CURRENT_SAVE_VERSION})"
+        ));
+    }
+
+    while version < CURRENT_SAVE_VERSION {
+        let migrate = MIGRATIONS
+            .get((version - 1) as usize)
+            .ok_or_else(|| color_eyre::eyre::eyre!("no migration registered for save version {version}"))?;
+        state = migrate(state);
+        version += 1;
+    }
+
+    serde_json::from_value(state).wrap_err("failed to deserialize migrated game state")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_FIXTURE: &str = r#"{
+        "resources": [
+            {
+                "resource_type": "Wood",
+                "amount": 12,
+                "level": 2,
+                "cost": { "amount": 2, "resource_type": "Wood" },
+                "progress": 0.25,
+                "progress_per_tick": 1.0
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn migrates_unversioned_legacy_save() {
+        let state = load_and_migrate(V1_FIXTURE).expect("legacy save should migrate");
+        assert_eq!(state.resources.len(), 1);
+        assert_eq!(state.resources[0].amount, 12);
+        assert_eq!(state.resources[0].level, 2);
+    }
+
+    #[test]
+    fn rejects_zero_version() {
+        let raw = r#"{"version": 0, "state": {}}"#;
+        let err = load_and_migrate(raw).expect_err("version 0 should be rejected, not underflow");
+        assert!(err.to_string().contains("unsupported version"));
+    }
+
+    #[test]
+    fn loads_current_versioned_save() {
+        let raw = serde_json::to_string(&SaveFile::new(GameState::default())).unwrap();
+        let state = load_and_migrate(&raw).expect("current save should load");
+        assert_eq!(
+            state.resources.len(),
+            GameState::default().resources.len()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::app::{Cost, GameState};
+use color_eyre::eyre::eyre;
+use mlua::{Function, Lua, LuaSerdeExt, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Errors are kept here instead of `eprintln!`-ed: `main` enters raw mode + the
+/// alternate screen before `ScriptEngine` is ever created, so stderr writes would
+/// garble the live display rather than being seen. Callers drain this into visible
+/// app state (see `App::script_errors`) instead.
+type ErrorLog = RefCell<Vec<String>>;
+
+/// Embedded Lua runtime that lets user scripts extend the per-tick economy and upgrade
+/// logic beyond the fixed `level * progress_per_tick` formula — e.g. resources that
+/// feed each other, prestige resets, or conditional production — without recompiling.
+pub struct ScriptEngine {
+    lua: Lua,
+    has_on_tick: bool,
+    has_on_upgrade: bool,
+    errors: ErrorLog,
+}
+
+impl fmt::Debug for ScriptEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptEngine")
+            .field("has_on_tick", &self.has_on_tick)
+            .field("has_on_upgrade", &self.has_on_upgrade)
+            .finish()
+    }
+}
+
+/// What a script's `on_upgrade` hook decided: whether the upgrade is permitted, and
+/// optionally a cost that overrides the resource's own `upgrade_cost()`.
+#[derive(Debug)]
+pub struct UpgradeDecision {
+    pub permitted: bool,
+    pub cost: Option<Cost>,
+}
+
+impl ScriptEngine {
+    /// Loads every `*.lua` file in `dir`. A missing directory just means no scripts are
+    /// installed, not an error; a script that fails to parse/run is recorded in
+    /// [`ScriptEngine::drain_errors`] and simply isn't registered.
+    pub fn load_from_dir(dir: &Path) -> Self {
+        let lua = Lua::new();
+        let errors = ErrorLog::default();
+
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("lua") {
+                    continue;
+                }
+                if let Err(err) = Self::load_script(&lua, &path) {
+                    errors
+                        .borrow_mut()
+                        .push(format!("failed to load script {}: {err:?}", path.display()));
+                }
+            }
+        }
+
+        let has_on_tick = lua.globals().contains_key("on_tick").unwrap_or(false);
+        let has_on_upgrade = lua.globals().contains_key("on_upgrade").unwrap_or(false);
+
+        Self {
+            lua,
+            has_on_tick,
+            has_on_upgrade,
+            errors,
+        }
+    }
+
+    fn load_script(lua: &Lua, path: &Path) -> color_eyre::Result<()> {
+        let source = fs::read_to_string(path)?;
+        lua.load(&source).set_name(path.display().to_string()).exec()?;
+        Ok(())
+    }
+
+    /// Takes every error recorded since the last call, for the caller to surface
+    /// somewhere visible (a log, a modal) rather than losing them to stderr.
+    pub fn drain_errors(&self) -> Vec<String> {
+        std::mem::take(&mut self.errors.borrow_mut())
+    }
+
+    /// Calls the scripts' `on_tick(state)` hook, if one is registered, letting scripts
+    /// extend the tick logic beyond the fixed formula.
+    ///
+    /// Contract: the hook receives the state table and must `return` it (mutating it
+    /// in place beforehand is fine and the idiomatic way to do it — returning a
+    /// *different* table also works). Returning nothing is treated as a script bug and
+    /// reported through [`ScriptEngine::drain_errors`], not silently ignored, since a
+    /// missing return would otherwise wipe the tick's progress every frame.
+    pub fn on_tick(&self, state: &mut GameState) {
+        if !self.has_on_tick {
+            return;
+        }
+        if let Err(err) = self.try_on_tick(state) {
+            self.errors.borrow_mut().push(format!("on_tick script hook failed: {err:?}"));
+        }
+    }
+
+    fn try_on_tick(&self, state: &mut GameState) -> color_eyre::Result<()> {
+        let table = self.lua.to_value(state)?;
+        let on_tick: Function = self.lua.globals().get("on_tick")?;
+        let result = on_tick.call(table)?;
+        if matches!(result, Value::Nil) {
+            return Err(eyre!(
+                "on_tick must `return state` (mutate the table in place, then return it); got nil"
+            ));
+        }
+        // `max_offline_secs` is skipped by serde, so it never reaches Lua; carry it over
+        // rather than letting the round trip reset it to `None` (uncapped).
+        let max_offline_secs = state.max_offline_secs;
+        *state = self.lua.from_value(result)?;
+        state.max_offline_secs = max_offline_secs;
+        Ok(())
+    }
+
+    /// Calls the scripts' `on_upgrade(state, index)` hook, if one is registered, and
+    /// returns its [`UpgradeDecision`]. A missing hook or a script error is treated as
+    /// "no opinion" (`None`), leaving the built-in cost check as the sole gate, so a
+    /// broken script can only ever be more permissive, never soft-lock upgrades
+    /// entirely.
+    ///
+    /// The Lua side returns `permitted, cost_amount, cost_resource` — the latter two
+    /// may be `nil` to leave the resource's own cost curve in place.
+    pub fn on_upgrade(&self, state: &GameState, index: usize) -> Option<UpgradeDecision> {
+        if !self.has_on_upgrade {
+            return None;
+        }
+        match self.try_on_upgrade(state, index) {
+            Ok(decision) => Some(decision),
+            Err(err) => {
+                self.errors
+                    .borrow_mut()
+                    .push(format!("on_upgrade script hook failed: {err:?}"));
+                None
+            }
+        }
+    }
+
+    fn try_on_upgrade(&self, state: &GameState, index: usize) -> color_eyre::Result<UpgradeDecision> {
+        let table = self.lua.to_value(state)?;
+        let on_upgrade: Function = self.lua.globals().get("on_upgrade")?;
+        let (permitted, cost_amount, cost_resource): (bool, Option<u64>, Option<String>) =
+            on_upgrade.call((table, index))?;
+        let cost = match (cost_amount, cost_resource) {
+            (Some(amount), Some(resource_type)) => Some(Cost::new(amount, resource_type)),
+            _ => None,
+        };
+        Ok(UpgradeDecision { permitted, cost })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::app::App;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, BorderType, Gauge};
+use ratatui::widgets::{Block, BorderType, Gauge, List, ListItem};
 use tui_widget_list::{ListBuilder, ListView};
 impl Widget for &App {
     /// Renders the user interface widgets.
@@ -10,13 +10,31 @@ impl Widget for &App {
     // - https://docs.rs/ratatui/latest/ratatui/widgets/index.html
     // - https://github.com/ratatui/ratatui/tree/master/examples
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let block = Block::bordered()
+        if let Some(summary) = &self.pending_summary {
+            render_offline_summary(summary, area, buf);
+            return;
+        }
+
+        if let Some(picker) = &self.slot_picker {
+            render_slot_picker(picker, area, buf);
+            return;
+        }
+
+        // Script errors are shown as a bottom banner rather than a blocking modal: a
+        // broken `on_tick` hook could otherwise append forever and permanently cover
+        // the game, which would be strictly worse than the stderr spam it replaces.
+        let mut block = Block::bordered()
             .title("incremental-tui")
             .title_alignment(Alignment::Center)
             .border_type(BorderType::Rounded);
+        if let Some(error) = self.script_errors.last() {
+            block = block
+                .title_bottom(format!("script error: {error}"))
+                .title_style(Style::default().fg(Color::Red));
+        }
 
         let builder = ListBuilder::new(|context| {
-            let resource = self.resources[context.index].clone();
+            let resource = self.game_state.resources[context.index].clone();
             let resource_label = format!("{} (Lvl {}): {}", resource.resource_type, resource.level, resource.amount);
             let resource_block = if context.is_selected {
                 let upgrade_str = "Press <Enter> to upgrade";
@@ -44,9 +62,86 @@ impl Widget for &App {
             (item, main_axis_size)
         });
 
-        let list = ListView::new(builder, self.resources.len())
+        let list = ListView::new(builder, self.game_state.resources.len())
             .block(block);
 
         list.render(area, buf, &mut self.list_state.borrow_mut());
     }
 }
+
+/// Renders the dismissible "welcome back" modal summarizing offline earnings.
+fn render_offline_summary(summary: &crate::app::OfflineSummary, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .title("Welcome back")
+        .title_alignment(Alignment::Center)
+        .title_bottom("Press any key to continue")
+        .border_type(BorderType::Rounded);
+
+    let away_secs = summary.time_away.as_secs();
+    let mut lines = vec![format!(
+        "You were away for {}h {}m {}s.",
+        away_secs / 3600,
+        (away_secs % 3600) / 60,
+        away_secs % 60
+    )];
+    if summary.gains.is_empty() {
+        lines.push("Nothing accrued while you were away.".to_string());
+    } else {
+        lines.push("While you were away, you gained:".to_string());
+        for (resource_type, gained) in &summary.gains {
+            lines.push(format!("  +{gained} {resource_type}"));
+        }
+    }
+
+    let items: Vec<ListItem> = lines.into_iter().map(ListItem::new).collect();
+    Widget::render(List::new(items).block(block), area, buf);
+}
+
+/// Renders the save-slot picker shown on startup when prior saves exist, including a
+/// trailing "New game" entry for starting a fresh slot.
+fn render_slot_picker(picker: &crate::app::SlotPicker, area: Rect, buf: &mut Buffer) {
+    if let Some(input) = &picker.new_slot_input {
+        render_new_slot_prompt(input, area, buf);
+        return;
+    }
+
+    let block = Block::bordered()
+        .title("Choose a save")
+        .title_alignment(Alignment::Center)
+        .title_bottom("<Up>/<Down> to choose, <Enter> to play")
+        .border_type(BorderType::Rounded);
+
+    let mut items: Vec<ListItem> = picker
+        .available
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| {
+            if index == picker.selected {
+                ListItem::new(format!("> {slot}")).style(Style::default().fg(Color::Green))
+            } else {
+                ListItem::new(format!("  {slot}"))
+            }
+        })
+        .collect();
+
+    let new_game_entry = if picker.selected == picker.available.len() {
+        ListItem::new("> + New game").style(Style::default().fg(Color::Green))
+    } else {
+        ListItem::new("  + New game")
+    };
+    items.push(new_game_entry);
+
+    Widget::render(List::new(items).block(block), area, buf);
+}
+
+/// Renders the text-entry prompt for naming a new save slot.
+fn render_new_slot_prompt(input: &str, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .title("Name your new game")
+        .title_alignment(Alignment::Center)
+        .title_bottom("<Enter> to confirm, <Esc> to cancel")
+        .border_type(BorderType::Rounded);
+
+    let items = vec![ListItem::new(format!("> {input}"))];
+    Widget::render(List::new(items).block(block), area, buf);
+}